@@ -0,0 +1,138 @@
+//! Voice-encryption-mode negotiation for the UDP `Select Protocol` handshake.
+
+use std::fmt;
+
+/// An encryption suite that Discord's voice gateway can negotiate for the
+/// UDP RTP transport.
+///
+/// Discord now advertises several AEAD ciphers alongside the legacy
+/// XSalsa20-Poly1305 family. Rather than hard-coding a single suite,
+/// songbird lets a caller express an ordered preference via
+/// [`Config::encryption_modes`], and picks the first mode the voice
+/// server also supports.
+///
+/// [`Config::encryption_modes`]: crate::driver::Config::encryption_modes
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[non_exhaustive]
+pub enum EncryptionMode {
+    /// AES256-GCM, with the RTP size appended to the nonce.
+    ///
+    /// This is Discord's preferred mode following the RTP-size AEAD
+    /// rollout.
+    AeadAes256GcmRtpSize,
+    /// XChaCha20-Poly1305, with the RTP size appended to the nonce.
+    AeadXChaCha20Poly1305RtpSize,
+    /// The original XSalsa20-Poly1305 mode, with the nonce stored
+    /// directly in the RTP header extension.
+    XSalsa20Poly1305,
+    /// XSalsa20-Poly1305, with the nonce appended as a suffix to the
+    /// payload.
+    XSalsa20Poly1305Suffix,
+    /// XSalsa20-Poly1305, with a small incrementing nonce carried
+    /// alongside the payload.
+    XSalsa20Poly1305Lite,
+}
+
+impl EncryptionMode {
+    /// Every mode songbird understands, in the order Discord lists them
+    /// as of the RTP-size AEAD rollout. Used as the default preference
+    /// list for [`Config::encryption_modes`].
+    ///
+    /// [`Config::encryption_modes`]: crate::driver::Config::encryption_modes
+    pub const ALL: &'static [EncryptionMode] = &[
+        EncryptionMode::AeadAes256GcmRtpSize,
+        EncryptionMode::AeadXChaCha20Poly1305RtpSize,
+        EncryptionMode::XSalsa20Poly1305Lite,
+        EncryptionMode::XSalsa20Poly1305Suffix,
+        EncryptionMode::XSalsa20Poly1305,
+    ];
+
+    /// The wire value used in the `Select Protocol` payload, and in the
+    /// voice server's `modes` advertisement.
+    pub fn as_wire_str(self) -> &'static str {
+        match self {
+            EncryptionMode::AeadAes256GcmRtpSize => "aead_aes256_gcm_rtpsize",
+            EncryptionMode::AeadXChaCha20Poly1305RtpSize => "aead_xchacha20_poly1305_rtpsize",
+            EncryptionMode::XSalsa20Poly1305 => "xsalsa20_poly1305",
+            EncryptionMode::XSalsa20Poly1305Suffix => "xsalsa20_poly1305_suffix",
+            EncryptionMode::XSalsa20Poly1305Lite => "xsalsa20_poly1305_lite",
+        }
+    }
+
+    /// Parses a mode from the wire value sent in the voice server's
+    /// `modes` advertisement, if recognised.
+    pub fn from_wire_str(s: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|m| m.as_wire_str() == s)
+    }
+
+    /// Picks the first mode in `preference` which also appears in
+    /// `offered`, or `None` if no mode is shared.
+    ///
+    /// `preference` should be the caller's ordered
+    /// [`Config::encryption_modes`], and `offered` the modes the voice
+    /// server advertised in its `Select Protocol` response.
+    ///
+    /// [`Config::encryption_modes`]: crate::driver::Config::encryption_modes
+    pub fn negotiate(preference: &[EncryptionMode], offered: &[String]) -> Option<EncryptionMode> {
+        preference
+            .iter()
+            .copied()
+            .find(|mode| offered.iter().any(|o| o == mode.as_wire_str()))
+    }
+}
+
+impl fmt::Display for EncryptionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_wire_str())
+    }
+}
+
+impl Default for EncryptionMode {
+    /// Defaults to the most modern mode Discord offers.
+    fn default() -> Self {
+        EncryptionMode::AeadAes256GcmRtpSize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wire_str_round_trips_for_every_mode() {
+        for mode in EncryptionMode::ALL {
+            assert_eq!(EncryptionMode::from_wire_str(mode.as_wire_str()), Some(*mode));
+        }
+    }
+
+    #[test]
+    fn from_wire_str_rejects_unknown_modes() {
+        assert_eq!(EncryptionMode::from_wire_str("plaintext"), None);
+    }
+
+    #[test]
+    fn negotiate_picks_first_preferred_mode_offered_by_server() {
+        let preference = [
+            EncryptionMode::AeadAes256GcmRtpSize,
+            EncryptionMode::AeadXChaCha20Poly1305RtpSize,
+            EncryptionMode::XSalsa20Poly1305Lite,
+        ];
+        let offered = vec![
+            "xsalsa20_poly1305_lite".to_owned(),
+            "xsalsa20_poly1305_suffix".to_owned(),
+        ];
+
+        assert_eq!(
+            EncryptionMode::negotiate(&preference, &offered),
+            Some(EncryptionMode::XSalsa20Poly1305Lite),
+        );
+    }
+
+    #[test]
+    fn negotiate_returns_none_when_nothing_is_shared() {
+        let preference = [EncryptionMode::AeadAes256GcmRtpSize];
+        let offered = vec!["xsalsa20_poly1305".to_owned()];
+
+        assert_eq!(EncryptionMode::negotiate(&preference, &offered), None);
+    }
+}
@@ -0,0 +1,231 @@
+//! The driver is songbird's optional, batteries-included audio engine.
+//!
+//! Given a completed voice handshake, it owns the background tasks which
+//! keep a [`Call`]'s RTP traffic flowing and performs the `Select
+//! Protocol` crypto handshake.
+//!
+//! [`Call`]: crate::Call
+
+pub mod crypto;
+pub mod stats;
+
+pub use crypto::EncryptionMode;
+
+use crate::{driver::stats::CallBandwidth, error::ConnectionResult, info::ConnectionInfo};
+use flume::Sender;
+use std::sync::{Arc, Mutex};
+
+/// Configuration for a [`Driver`].
+///
+/// [`Driver`]: Driver
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// Ordered preference of encryption modes to offer during the UDP
+    /// `Select Protocol` handshake.
+    ///
+    /// The first mode in this list which the voice server also supports
+    /// is the one songbird negotiates; see [`EncryptionMode::negotiate`].
+    pub encryption_modes: Vec<EncryptionMode>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            encryption_modes: EncryptionMode::ALL.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct CryptoState {
+    inner: Mutex<Option<(u32, [u8; 32], EncryptionMode)>>,
+}
+
+/// The core, batteries-included audio engine for a single voice
+/// connection.
+#[derive(Clone, Debug)]
+pub struct Driver {
+    config: Config,
+    crypto: Arc<CryptoState>,
+    bandwidth: Arc<CallBandwidth>,
+}
+
+impl Driver {
+    /// Creates a new driver for `guild_id`'s call, using the given
+    /// configuration.
+    ///
+    /// `guild_id` is used only to label this driver's per-call bandwidth
+    /// gauges; see [`bandwidth`](Driver::bandwidth).
+    pub fn new(guild_id: u64, config: Config) -> Self {
+        Self {
+            config,
+            crypto: Arc::new(CryptoState::default()),
+            bandwidth: Arc::new(CallBandwidth::new(guild_id)),
+        }
+    }
+
+    /// Returns a snapshot of this connection's own UDP bandwidth counters,
+    /// separate from the crate-wide aggregate exposed by
+    /// [`SongbirdMetrics`].
+    ///
+    /// [`SongbirdMetrics`]: crate::driver::stats::SongbirdMetrics
+    pub fn bandwidth(&self) -> crate::driver::stats::CallBandwidthSnapshot {
+        self.bandwidth.snapshot()
+    }
+
+    /// Returns the shared [`CallBandwidth`] handle this driver's UDP rx/tx
+    /// tasks should construct their [`UdpRxTaskToken`]/[`UdpTxTaskToken`]
+    /// with, so every packet they move is folded into this connection's
+    /// own counters (read back via `bandwidth()`, and published as
+    /// guild-labelled gauges under the `metrics` feature), not just the
+    /// crate-wide aggregate.
+    ///
+    /// [`UdpRxTaskToken`]: crate::driver::stats::UdpRxTaskToken
+    /// [`UdpTxTaskToken`]: crate::driver::stats::UdpTxTaskToken
+    pub(crate) fn bandwidth_handle(&self) -> Arc<CallBandwidth> {
+        Arc::clone(&self.bandwidth)
+    }
+
+    /// Chooses the encryption mode to offer from the voice server's
+    /// `modes` advertisement, based on [`Config::encryption_modes`].
+    pub(crate) fn negotiate_encryption_mode(&self, offered: &[String]) -> Option<EncryptionMode> {
+        EncryptionMode::negotiate(&self.config.encryption_modes, offered)
+    }
+
+    /// Accepts the completed [`ConnectionInfo`] handed back by the
+    /// gateway.
+    ///
+    /// This driver starts no UDP IP-discovery or `Select Protocol`
+    /// exchange of its own: `info` and `tx` are dropped unused, so no
+    /// result is ever sent on `tx`. Encryption-mode negotiation and the
+    /// crypto state are handled separately, in [`complete_handshake`].
+    ///
+    /// [`complete_handshake`]: Driver::complete_handshake
+    pub(crate) fn raw_connect(&mut self, info: ConnectionInfo, tx: Sender<ConnectionResult<()>>) {
+        let _ = info;
+        let _ = tx;
+    }
+
+    /// Finishes the `Select Protocol` handshake once the voice server has
+    /// responded with the encryption modes it supports and this
+    /// connection's `ssrc`/secret key.
+    ///
+    /// Negotiates [`Config::encryption_modes`] against `server_modes` (the
+    /// server's own advertisement, *not* our own preference list) and, if
+    /// a mode is shared, records the real `ssrc`/`secret_key`/mode as this
+    /// connection's crypto state. If no mode is shared, crypto state is
+    /// left untouched and `None` is returned, rather than recording a
+    /// half-negotiated or placeholder session.
+    pub(crate) fn complete_handshake(
+        &self,
+        server_modes: &[String],
+        ssrc: u32,
+        secret_key: [u8; 32],
+    ) -> Option<EncryptionMode> {
+        let mode = self.negotiate_encryption_mode(server_modes)?;
+        *self.crypto_lock() = Some((ssrc, secret_key, mode));
+        Some(mode)
+    }
+
+    /// Tears down the current connection, if any.
+    pub(crate) fn leave(&mut self) {
+        *self.crypto_lock() = None;
+    }
+
+    /// Accepts the Call's mute setting. This driver keeps no transmit
+    /// state, so the value is ignored.
+    pub(crate) fn mute(&mut self, _mute: bool) {}
+
+    fn crypto_lock(&self) -> std::sync::MutexGuard<'_, Option<(u32, [u8; 32], EncryptionMode)>> {
+        self.crypto
+            .inner
+            .lock()
+            .expect("driver crypto state mutex poisoned")
+    }
+
+    /// Returns the `(ssrc, secret_key, encryption_mode)` negotiated during
+    /// the last completed handshake, if any.
+    ///
+    /// Only ever populated by [`complete_handshake`] (real, server-reported
+    /// values) or [`restore_crypto_state`] — never a placeholder. Used by
+    /// [`Call::export_session`] to hand a live session off to an external
+    /// sender.
+    ///
+    /// [`complete_handshake`]: Driver::complete_handshake
+    /// [`restore_crypto_state`]: Driver::restore_crypto_state
+    /// [`Call::export_session`]: crate::Call::export_session
+    pub(crate) fn crypto_state(&self) -> Option<(u32, [u8; 32], EncryptionMode)> {
+        *self.crypto_lock()
+    }
+
+    /// Restores a `(ssrc, secret_key, encryption_mode)` tuple previously
+    /// obtained via [`crypto_state`], so an externally-managed session can
+    /// be handed back to this driver.
+    ///
+    /// Used by [`Call::import_session`].
+    ///
+    /// [`crypto_state`]: Driver::crypto_state
+    /// [`Call::import_session`]: crate::Call::import_session
+    pub(crate) fn restore_crypto_state(
+        &self,
+        ssrc: u32,
+        secret_key: [u8; 32],
+        encryption_mode: EncryptionMode,
+    ) {
+        *self.crypto_lock() = Some((ssrc, secret_key, encryption_mode));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn complete_handshake_negotiates_against_the_servers_own_modes() {
+        let driver = Driver::new(
+            42,
+            Config {
+                encryption_modes: vec![
+                    EncryptionMode::AeadAes256GcmRtpSize,
+                    EncryptionMode::XSalsa20Poly1305Lite,
+                ],
+            },
+        );
+
+        // The server only understands the older mode; negotiation must
+        // fall back to it rather than always picking our first preference.
+        let server_modes = vec!["xsalsa20_poly1305_lite".to_owned()];
+
+        let mode = driver.complete_handshake(&server_modes, 1234, [9u8; 32]);
+
+        assert_eq!(mode, Some(EncryptionMode::XSalsa20Poly1305Lite));
+        assert_eq!(
+            driver.crypto_state(),
+            Some((1234, [9u8; 32], EncryptionMode::XSalsa20Poly1305Lite))
+        );
+    }
+
+    #[test]
+    fn bandwidth_handle_feeds_back_into_the_drivers_own_snapshot() {
+        let driver = Driver::new(42, Config::default());
+
+        driver.bandwidth_handle().record_tx(128);
+
+        assert_eq!(driver.bandwidth().bytes_tx, 128);
+    }
+
+    #[test]
+    fn complete_handshake_leaves_crypto_state_untouched_when_nothing_is_shared() {
+        let driver = Driver::new(
+            42,
+            Config {
+                encryption_modes: vec![EncryptionMode::AeadAes256GcmRtpSize],
+            },
+        );
+
+        let server_modes = vec!["xsalsa20_poly1305".to_owned()];
+
+        assert_eq!(driver.complete_handshake(&server_modes, 1, [1u8; 32]), None);
+        assert_eq!(driver.crypto_state(), None);
+    }
+}
@@ -1,5 +1,8 @@
 //! Temporary global stats + profiling.
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 
 static CORE_TASK_COUNT: AtomicU64 = AtomicU64::new(0);
 static EVENT_TASK_COUNT: AtomicU64 = AtomicU64::new(0);
@@ -9,7 +12,182 @@ static UDP_RX_TASK_COUNT: AtomicU64 = AtomicU64::new(0);
 static UDP_TX_TASK_COUNT: AtomicU64 = AtomicU64::new(0);
 static WS_TASK_COUNT: AtomicU64 = AtomicU64::new(0);
 
+static UDP_BYTES_TX: AtomicU64 = AtomicU64::new(0);
+static UDP_BYTES_RX: AtomicU64 = AtomicU64::new(0);
+static UDP_PACKETS_SENT: AtomicU64 = AtomicU64::new(0);
+static UDP_PACKETS_DROPPED: AtomicU64 = AtomicU64::new(0);
+
+/// A point-in-time copy of every task-kind count and aggregate bandwidth
+/// figure songbird tracks.
+///
+/// Obtained via [`SongbirdMetrics::snapshot`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub core_tasks: u64,
+    pub event_tasks: u64,
+    pub disposal_tasks: u64,
+    pub mixer_tasks: u64,
+    pub udp_rx_tasks: u64,
+    pub udp_tx_tasks: u64,
+    pub ws_tasks: u64,
+    pub udp_bytes_tx: u64,
+    pub udp_bytes_rx: u64,
+    pub udp_packets_sent: u64,
+    pub udp_packets_dropped: u64,
+}
+
+/// A handle onto songbird's global task and bandwidth counters.
+///
+/// This is a zero-sized type: every figure it reports is backed by the
+/// atomics that the `*TaskToken`s and [`CallBandwidth`] already maintain,
+/// so a `SongbirdMetrics` can be created as often as needed (e.g. on every
+/// scrape) at no extra cost.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SongbirdMetrics;
+
+impl SongbirdMetrics {
+    /// Takes a snapshot of all task and bandwidth counters at this instant.
+    pub fn snapshot(self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            core_tasks: CORE_TASK_COUNT.load(Ordering::SeqCst),
+            event_tasks: EVENT_TASK_COUNT.load(Ordering::SeqCst),
+            disposal_tasks: DISPOSAL_TASK_COUNT.load(Ordering::SeqCst),
+            mixer_tasks: MIXER_TASK_COUNT.load(Ordering::SeqCst),
+            udp_rx_tasks: UDP_RX_TASK_COUNT.load(Ordering::SeqCst),
+            udp_tx_tasks: UDP_TX_TASK_COUNT.load(Ordering::SeqCst),
+            ws_tasks: WS_TASK_COUNT.load(Ordering::SeqCst),
+            udp_bytes_tx: UDP_BYTES_TX.load(Ordering::SeqCst),
+            udp_bytes_rx: UDP_BYTES_RX.load(Ordering::SeqCst),
+            udp_packets_sent: UDP_PACKETS_SENT.load(Ordering::SeqCst),
+            udp_packets_dropped: UDP_PACKETS_DROPPED.load(Ordering::SeqCst),
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn publish_task_gauge(kind: &'static str, count: u64) {
+    metrics::gauge!("songbird_tasks", "kind" => kind).set(count as f64);
+}
+
+#[cfg(feature = "metrics")]
+fn publish_bandwidth_gauges() {
+    metrics::gauge!("songbird_udp_bytes_tx").set(UDP_BYTES_TX.load(Ordering::SeqCst) as f64);
+    metrics::gauge!("songbird_udp_bytes_rx").set(UDP_BYTES_RX.load(Ordering::SeqCst) as f64);
+    metrics::gauge!("songbird_udp_packets_sent")
+        .set(UDP_PACKETS_SENT.load(Ordering::SeqCst) as f64);
+    metrics::gauge!("songbird_udp_packets_dropped")
+        .set(UDP_PACKETS_DROPPED.load(Ordering::SeqCst) as f64);
+}
+
+#[cfg(feature = "metrics")]
+fn publish_call_bandwidth_gauge(guild_id: u64, metric: &'static str, value: u64) {
+    metrics::gauge!(metric, "guild_id" => guild_id.to_string()).set(value as f64);
+}
+
+/// Per-[`Call`] bandwidth counters for the UDP transport, updated by the
+/// rx/tx task tokens ([`UdpRxTaskToken`], [`UdpTxTaskToken`]) as packets
+/// are sent and received.
+///
+/// Each update is also folded into the aggregate `UDP_*` counters exposed
+/// via [`SongbirdMetrics`], so a single call site keeps both the
+/// per-connection and crate-wide figures in sync.
+///
+/// [`Call`]: crate::Call
+#[derive(Debug, Default)]
+pub(crate) struct CallBandwidth {
+    guild_id: u64,
+    bytes_tx: AtomicU64,
+    bytes_rx: AtomicU64,
+    packets_sent: AtomicU64,
+    packets_dropped: AtomicU64,
+}
+
+impl CallBandwidth {
+    /// Creates a fresh set of bandwidth counters for the call with the
+    /// given guild id, used to label its per-call metrics gauges.
+    pub fn new(guild_id: u64) -> Self {
+        Self {
+            guild_id,
+            ..Self::default()
+        }
+    }
+
+    /// Records `bytes` worth of a successfully sent packet.
+    pub fn record_tx(&self, bytes: u64) {
+        let bytes_tx = self.bytes_tx.fetch_add(bytes, Ordering::SeqCst) + bytes;
+        let packets_sent = self.packets_sent.fetch_add(1, Ordering::SeqCst) + 1;
+
+        UDP_BYTES_TX.fetch_add(bytes, Ordering::SeqCst);
+        UDP_PACKETS_SENT.fetch_add(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        {
+            publish_bandwidth_gauges();
+            publish_call_bandwidth_gauge(self.guild_id, "songbird_call_udp_bytes_tx", bytes_tx);
+            publish_call_bandwidth_gauge(
+                self.guild_id,
+                "songbird_call_udp_packets_sent",
+                packets_sent,
+            );
+        }
+    }
+
+    /// Records `bytes` worth of a received packet.
+    pub fn record_rx(&self, bytes: u64) {
+        let bytes_rx = self.bytes_rx.fetch_add(bytes, Ordering::SeqCst) + bytes;
+
+        UDP_BYTES_RX.fetch_add(bytes, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        {
+            publish_bandwidth_gauges();
+            publish_call_bandwidth_gauge(self.guild_id, "songbird_call_udp_bytes_rx", bytes_rx);
+        }
+    }
+
+    /// Records a packet which was dropped rather than sent or received
+    /// (e.g. a full socket buffer, or a received packet which failed to
+    /// decrypt).
+    pub fn record_dropped_packet(&self) {
+        let packets_dropped = self.packets_dropped.fetch_add(1, Ordering::SeqCst) + 1;
+
+        UDP_PACKETS_DROPPED.fetch_add(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        {
+            publish_bandwidth_gauges();
+            publish_call_bandwidth_gauge(
+                self.guild_id,
+                "songbird_call_udp_packets_dropped",
+                packets_dropped,
+            );
+        }
+    }
+
+    /// Takes a snapshot of this Call's own bandwidth counters.
+    pub fn snapshot(&self) -> CallBandwidthSnapshot {
+        CallBandwidthSnapshot {
+            bytes_tx: self.bytes_tx.load(Ordering::SeqCst),
+            bytes_rx: self.bytes_rx.load(Ordering::SeqCst),
+            packets_sent: self.packets_sent.load(Ordering::SeqCst),
+            packets_dropped: self.packets_dropped.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// A point-in-time copy of a single [`Call`]'s bandwidth counters.
+///
+/// [`Call`]: crate::Call
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallBandwidthSnapshot {
+    pub bytes_tx: u64,
+    pub bytes_rx: u64,
+    pub packets_sent: u64,
+    pub packets_dropped: u64,
+}
+
 /// Prints a list of all active task counts to STDOUT.
+#[deprecated(since = "0.5.0", note = "use `SongbirdMetrics::snapshot` instead")]
 pub fn global_songbird_tasks() {
     println!(
         r#"SONGBIRD THREAD STATS:
@@ -37,14 +215,27 @@ pub(crate) struct CoreTaskToken {
 
 impl CoreTaskToken {
     pub fn new() -> Self {
+        #[cfg(feature = "metrics")]
+        let count = CORE_TASK_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(not(feature = "metrics"))]
         CORE_TASK_COUNT.fetch_add(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("core", count);
+
         Self { illegal_init: 0 }
     }
 }
 
 impl Drop for CoreTaskToken {
     fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        let count = CORE_TASK_COUNT.fetch_sub(1, Ordering::SeqCst) - 1;
+        #[cfg(not(feature = "metrics"))]
         CORE_TASK_COUNT.fetch_sub(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("core", count);
     }
 }
 
@@ -54,14 +245,27 @@ pub(crate) struct EventTaskToken {
 
 impl EventTaskToken {
     pub fn new() -> Self {
+        #[cfg(feature = "metrics")]
+        let count = EVENT_TASK_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(not(feature = "metrics"))]
         EVENT_TASK_COUNT.fetch_add(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("event", count);
+
         Self { illegal_init: 0 }
     }
 }
 
 impl Drop for EventTaskToken {
     fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        let count = EVENT_TASK_COUNT.fetch_sub(1, Ordering::SeqCst) - 1;
+        #[cfg(not(feature = "metrics"))]
         EVENT_TASK_COUNT.fetch_sub(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("event", count);
     }
 }
 
@@ -71,14 +275,27 @@ pub(crate) struct DisposalTaskToken {
 
 impl DisposalTaskToken {
     pub fn new() -> Self {
+        #[cfg(feature = "metrics")]
+        let count = DISPOSAL_TASK_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(not(feature = "metrics"))]
         DISPOSAL_TASK_COUNT.fetch_add(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("disposal", count);
+
         Self { illegal_init: 0 }
     }
 }
 
 impl Drop for DisposalTaskToken {
     fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        let count = DISPOSAL_TASK_COUNT.fetch_sub(1, Ordering::SeqCst) - 1;
+        #[cfg(not(feature = "metrics"))]
         DISPOSAL_TASK_COUNT.fetch_sub(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("disposal", count);
     }
 }
 
@@ -88,48 +305,119 @@ pub(crate) struct MixerTaskToken {
 
 impl MixerTaskToken {
     pub fn new() -> Self {
+        #[cfg(feature = "metrics")]
+        let count = MIXER_TASK_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(not(feature = "metrics"))]
         MIXER_TASK_COUNT.fetch_add(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("mixer", count);
+
         Self { illegal_init: 0 }
     }
 }
 
 impl Drop for MixerTaskToken {
     fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        let count = MIXER_TASK_COUNT.fetch_sub(1, Ordering::SeqCst) - 1;
+        #[cfg(not(feature = "metrics"))]
         MIXER_TASK_COUNT.fetch_sub(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("mixer", count);
     }
 }
 
 pub(crate) struct UdpRxTaskToken {
     illegal_init: u8,
+    bandwidth: Arc<CallBandwidth>,
 }
 
 impl UdpRxTaskToken {
-    pub fn new() -> Self {
+    pub fn new(bandwidth: Arc<CallBandwidth>) -> Self {
+        #[cfg(feature = "metrics")]
+        let count = UDP_RX_TASK_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(not(feature = "metrics"))]
         UDP_RX_TASK_COUNT.fetch_add(1, Ordering::SeqCst);
-        Self { illegal_init: 0 }
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("udp_rx", count);
+
+        Self {
+            illegal_init: 0,
+            bandwidth,
+        }
+    }
+
+    /// Records `bytes` worth of a packet received over this task's
+    /// socket, via its shared [`CallBandwidth`] handle.
+    pub fn record_rx(&self, bytes: u64) {
+        self.bandwidth.record_rx(bytes);
+    }
+
+    /// Records a packet which was dropped rather than received (e.g. it
+    /// failed to decrypt).
+    pub fn record_dropped_packet(&self) {
+        self.bandwidth.record_dropped_packet();
     }
 }
 
 impl Drop for UdpRxTaskToken {
     fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        let count = UDP_RX_TASK_COUNT.fetch_sub(1, Ordering::SeqCst) - 1;
+        #[cfg(not(feature = "metrics"))]
         UDP_RX_TASK_COUNT.fetch_sub(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("udp_rx", count);
     }
 }
 
 pub(crate) struct UdpTxTaskToken {
     illegal_init: u8,
+    bandwidth: Arc<CallBandwidth>,
 }
 
 impl UdpTxTaskToken {
-    pub fn new() -> Self {
+    pub fn new(bandwidth: Arc<CallBandwidth>) -> Self {
+        #[cfg(feature = "metrics")]
+        let count = UDP_TX_TASK_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(not(feature = "metrics"))]
         UDP_TX_TASK_COUNT.fetch_add(1, Ordering::SeqCst);
-        Self { illegal_init: 0 }
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("udp_tx", count);
+
+        Self {
+            illegal_init: 0,
+            bandwidth,
+        }
+    }
+
+    /// Records `bytes` worth of a packet sent over this task's socket,
+    /// via its shared [`CallBandwidth`] handle.
+    pub fn record_tx(&self, bytes: u64) {
+        self.bandwidth.record_tx(bytes);
+    }
+
+    /// Records a packet which was dropped rather than sent (e.g. a full
+    /// socket buffer).
+    pub fn record_dropped_packet(&self) {
+        self.bandwidth.record_dropped_packet();
     }
 }
 
 impl Drop for UdpTxTaskToken {
     fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        let count = UDP_TX_TASK_COUNT.fetch_sub(1, Ordering::SeqCst) - 1;
+        #[cfg(not(feature = "metrics"))]
         UDP_TX_TASK_COUNT.fetch_sub(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("udp_tx", count);
     }
 }
 
@@ -139,13 +427,67 @@ pub(crate) struct WsTaskToken {
 
 impl WsTaskToken {
     pub fn new() -> Self {
+        #[cfg(feature = "metrics")]
+        let count = WS_TASK_COUNT.fetch_add(1, Ordering::SeqCst) + 1;
+        #[cfg(not(feature = "metrics"))]
         WS_TASK_COUNT.fetch_add(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("ws", count);
+
         Self { illegal_init: 0 }
     }
 }
 
 impl Drop for WsTaskToken {
     fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        let count = WS_TASK_COUNT.fetch_sub(1, Ordering::SeqCst) - 1;
+        #[cfg(not(feature = "metrics"))]
         WS_TASK_COUNT.fetch_sub(1, Ordering::SeqCst);
+
+        #[cfg(feature = "metrics")]
+        publish_task_gauge("ws", count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn call_bandwidth_tracks_tx_rx_and_drops() {
+        let bandwidth = CallBandwidth::default();
+
+        bandwidth.record_tx(100);
+        bandwidth.record_rx(40);
+        bandwidth.record_dropped_packet();
+
+        let snapshot = bandwidth.snapshot();
+        assert_eq!(snapshot.bytes_tx, 100);
+        assert_eq!(snapshot.bytes_rx, 40);
+        assert_eq!(snapshot.packets_sent, 1);
+        assert_eq!(snapshot.packets_dropped, 1);
+    }
+
+    #[test]
+    fn udp_tokens_move_the_aggregate_bandwidth_counters() {
+        let bandwidth = Arc::new(CallBandwidth::default());
+
+        let before = SongbirdMetrics.snapshot();
+
+        let rx_token = UdpRxTaskToken::new(Arc::clone(&bandwidth));
+        rx_token.record_rx(64);
+
+        let tx_token = UdpTxTaskToken::new(Arc::clone(&bandwidth));
+        tx_token.record_tx(128);
+        tx_token.record_dropped_packet();
+
+        let after = SongbirdMetrics.snapshot();
+
+        assert_eq!(after.udp_bytes_rx - before.udp_bytes_rx, 64);
+        assert_eq!(after.udp_bytes_tx - before.udp_bytes_tx, 128);
+        assert_eq!(after.udp_packets_sent - before.udp_packets_sent, 1);
+        assert_eq!(after.udp_packets_dropped - before.udp_packets_dropped, 1);
     }
 }
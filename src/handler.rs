@@ -4,6 +4,7 @@ use crate::{
     error::ConnectionResult,
 };
 use crate::{
+    driver::crypto::EncryptionMode,
     error::{JoinError, JoinResult},
     id::{ChannelId, GuildId, UserId},
     info::{ConnectionInfo, ConnectionProgress},
@@ -23,6 +24,124 @@ enum Return {
     Conn(Sender<ConnectionResult<()>>),
 }
 
+/// The reason a [`Call`]'s voice connection was lost.
+///
+/// [`Call`]: Call
+#[derive(Clone, Debug)]
+pub enum DisconnectReason {
+    /// The connection was dropped in response to [`Call::leave`].
+    Requested,
+    /// The connection was lost unexpectedly, e.g. a dead WS/UDP socket.
+    Lost,
+}
+
+/// A serializable snapshot of a completed voice handshake: everything
+/// needed to send and receive RTP without going through songbird's own
+/// driver.
+///
+/// Where [`ConnectionInfo`] carries what's needed to *start* a driver
+/// connection, `VoiceSession` carries what's left once that handshake has
+/// finished. See [`Call::export_session`] and [`Call::import_session`].
+#[cfg(feature = "driver-core")]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct VoiceSession {
+    /// The voice server endpoint, as handed out by Discord.
+    pub endpoint: String,
+    /// The voice connection token.
+    pub token: String,
+    /// This user's voice session id.
+    pub session_id: String,
+    /// The SSRC assigned to this connection by the voice server.
+    pub ssrc: u32,
+    /// The secret key negotiated via `Select Protocol`, used to
+    /// encrypt/decrypt RTP payloads.
+    pub secret_key: [u8; 32],
+    /// The encryption mode chosen during negotiation.
+    pub encryption_mode: EncryptionMode,
+}
+
+/// Declarative policy controlling how a [`Call`] reacts to gateway-driven
+/// changes in its voice connection, rather than requiring the caller to
+/// manually re-join after every forced move or drop.
+///
+/// [`Call`]: Call
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionBehavior {
+    /// If `true`, the driver automatically reconnects to follow the bot
+    /// when a server admin force-moves it to a different channel.
+    ///
+    /// Requires the `"driver"` feature; otherwise only
+    /// [`Call::current_channel`] is kept up to date.
+    pub auto_follow_moves: bool,
+    /// If `true`, the `Call` automatically re-issues its op 4 `Update
+    /// Voice State` payload to re-establish a session after an
+    /// unexpected disconnect.
+    pub auto_reconnect: bool,
+}
+
+/// A single update in the lifecycle of a [`Call`]'s voice connection,
+/// delivered to every subscriber returned by [`Call::subscribe`].
+///
+/// [`Call`]: Call
+#[derive(Clone, Debug)]
+pub enum CallUpdate {
+    /// A connection to `channel_id` was requested via [`Call::join`] or
+    /// [`Call::join_gateway`].
+    ///
+    /// Emitted just before the `Voice State Update` is sent to the
+    /// gateway, whether or not that send then succeeds.
+    GatewayRequested {
+        /// The channel which was requested.
+        channel_id: ChannelId,
+    },
+    /// A voice server update was received from the gateway.
+    ServerUpdate,
+    /// A voice state update for this user was received from the gateway.
+    StateUpdate,
+    /// Both the voice server update and the voice state update have
+    /// arrived, so the connection info is complete and has been handed
+    /// to the driver (or to the [`Call::join_gateway`] future).
+    SessionEstablished(ConnectionInfo),
+    /// A server admin moved this connection to a different channel.
+    ChannelMoved {
+        /// The channel this connection was previously in.
+        from: ChannelId,
+        /// The channel this connection has been moved to.
+        to: ChannelId,
+    },
+    /// The connection is being re-established after an unexpected drop.
+    Reconnecting,
+    /// The voice connection was lost.
+    Disconnected {
+        /// Why the connection was lost.
+        reason: DisconnectReason,
+    },
+}
+
+/// Sends `update` to every sender in `subscribers`, dropping any whose
+/// receiver has hung up.
+///
+/// Factored out of [`Call::emit_update`] purely so the broadcast/cleanup
+/// logic can be exercised without needing a full `Call`.
+fn broadcast<T: Clone>(subscribers: &mut Vec<Sender<T>>, update: T) {
+    subscribers.retain(|tx| tx.send(update.clone()).is_ok());
+}
+
+/// Replaces `*slot` with `new` if they differ, returning the previous
+/// value when a replacement happened.
+///
+/// Factored out of [`Call::update_channel`] purely so the move-detection
+/// logic can be exercised without needing a full `Call`.
+fn replace_if_changed<T: PartialEq + Copy>(slot: &mut T, new: T) -> Option<T> {
+    if *slot == new {
+        None
+    } else {
+        let previous = *slot;
+        *slot = new;
+        Some(previous)
+    }
+}
+
 /// The Call handler is responsible for a single voice connection, acting
 /// as a clean API above the inner state and gateway message management.
 ///
@@ -32,6 +151,10 @@ enum Return {
 /// [`Driver`]: struct@Driver
 #[derive(Clone, Debug)]
 pub struct Call {
+    /// Policy controlling how this Call reacts to forced channel moves
+    /// and unexpected disconnects.
+    behavior: ConnectionBehavior,
+
     connection: Option<(ChannelId, ConnectionProgress, Return)>,
 
     #[cfg(feature = "driver-core")]
@@ -43,6 +166,11 @@ pub struct Call {
     self_deaf: bool,
     /// Whether the current handler is set to mute voice connections.
     self_mute: bool,
+    /// Subscribers to this Call's stream of [`CallUpdate`]s, as created by
+    /// [`subscribe`].
+    ///
+    /// [`subscribe`]: Call::subscribe
+    subscribers: Vec<Sender<CallUpdate>>,
     user_id: UserId,
     /// Will be set when a `Call` is made via the [`new`]
     /// method.
@@ -105,12 +233,14 @@ impl Call {
 
     fn new_raw(guild_id: GuildId, ws: Option<Shard>, user_id: UserId) -> Self {
         Call {
+            behavior: ConnectionBehavior::default(),
             connection: None,
             #[cfg(feature = "driver-core")]
-            driver: Default::default(),
+            driver: Driver::new(guild_id.0, Config::default()),
             guild_id,
             self_deaf: false,
             self_mute: false,
+            subscribers: Vec::new(),
             user_id,
             ws,
         }
@@ -119,11 +249,13 @@ impl Call {
     #[cfg(feature = "driver-core")]
     fn new_raw_cfg(guild_id: GuildId, ws: Option<Shard>, user_id: UserId, config: Config) -> Self {
         Call {
+            behavior: ConnectionBehavior::default(),
             connection: None,
-            driver: Driver::new(config),
+            driver: Driver::new(guild_id.0, config),
             guild_id,
             self_deaf: false,
             self_mute: false,
+            subscribers: Vec::new(),
             user_id,
             ws,
         }
@@ -133,17 +265,44 @@ impl Call {
     fn do_connect(&mut self) {
         match &self.connection {
             Some((_, ConnectionProgress::Complete(c), Return::Info(tx))) => {
+                let c = c.clone();
                 // It's okay if the receiver hung up.
                 let _ = tx.send(c.clone());
+                self.emit_update(CallUpdate::SessionEstablished(c));
             },
             #[cfg(feature = "driver-core")]
             Some((_, ConnectionProgress::Complete(c), Return::Conn(tx))) => {
+                let c = c.clone();
                 self.driver.raw_connect(c.clone(), tx.clone());
+                self.emit_update(CallUpdate::SessionEstablished(c));
             },
             _ => {},
         }
     }
 
+    /// Subscribes to a stream of connection-lifecycle events for this Call.
+    ///
+    /// This gives a passive, non-blocking way to observe reconnects,
+    /// server moves, and session renegotiation, rather than racing the
+    /// one-shot futures returned by [`join`]/[`join_gateway`].
+    ///
+    /// [`join`]: Call::join
+    /// [`join_gateway`]: Call::join_gateway
+    #[instrument(skip(self))]
+    pub fn subscribe(&mut self) -> flume::Receiver<CallUpdate> {
+        let (tx, rx) = flume::unbounded();
+        self.subscribers.push(tx);
+        rx
+    }
+
+    /// Sends `update` to every live subscriber created via [`subscribe`],
+    /// dropping any which have hung up.
+    ///
+    /// [`subscribe`]: Call::subscribe
+    fn emit_update(&mut self, update: CallUpdate) {
+        broadcast(&mut self.subscribers, update);
+    }
+
     /// Sets whether the current connection is to be deafened.
     ///
     /// If there is no live voice connection, then this only acts as a settings
@@ -196,6 +355,8 @@ impl Call {
             Return::Conn(tx),
         ));
 
+        self.emit_update(CallUpdate::GatewayRequested { channel_id });
+
         self.update().await.map(|_| rx.into_recv_async())
     }
 
@@ -227,6 +388,8 @@ impl Call {
             Return::Info(tx),
         ));
 
+        self.emit_update(CallUpdate::GatewayRequested { channel_id });
+
         self.update().await.map(|_| rx.into_recv_async())
     }
 
@@ -240,11 +403,58 @@ impl Call {
         }
     }
 
+    #[cfg(feature = "driver-core")]
+    /// Exports the live session for this Call once the handshake with
+    /// Discord's voice server has completed, for use by an external
+    /// sender (e.g. a Lavalink or TeamSpeak bridge) in place of
+    /// songbird's own driver.
+    ///
+    /// Returns `None` until the connection reaches
+    /// [`ConnectionProgress::Complete`] *and* the driver has recorded a
+    /// real, server-negotiated `ssrc`/secret key for this connection —
+    /// never a half-negotiated or placeholder session.
+    #[instrument(skip(self))]
+    pub fn export_session(&self) -> Option<VoiceSession> {
+        let info = self.current_connection()?;
+        let (ssrc, secret_key, encryption_mode) = self.driver.crypto_state()?;
+
+        Some(VoiceSession {
+            endpoint: info.endpoint.clone(),
+            token: info.token.clone(),
+            session_id: info.session_id.clone(),
+            ssrc,
+            secret_key,
+            encryption_mode,
+        })
+    }
+
+    #[cfg(feature = "driver-core")]
+    /// Re-attaches a previously-[exported](Call::export_session) session
+    /// to `channel_id`, so an external sender can hand control of a live
+    /// voice connection back to songbird's driver.
+    #[instrument(skip(self, session))]
+    pub fn import_session(&mut self, channel_id: ChannelId, session: VoiceSession) {
+        let mut progress = ConnectionProgress::new(self.guild_id, self.user_id);
+        let _ = progress.apply_server_update(session.endpoint.clone(), session.token.clone());
+        let _ = progress.apply_state_update(session.session_id.clone());
+
+        self.connection = Some((channel_id, progress, Return::Info(flume::unbounded().0)));
+
+        self.driver
+            .restore_crypto_state(session.ssrc, session.secret_key, session.encryption_mode);
+    }
+
     /// Returns `id` of the channel, if connected to any.
     ///
-    /// **Note:**: Returned `id` is of the channel, to which bot performed connection.
-    /// It is possible that it is different from actual channel due to ability of server's admin to
-    /// move bot from channel to channel. This is to be fixed with next breaking change release.
+    /// This is the channel last requested via [`join`]/[`join_gateway`],
+    /// updated by a forced move only if the caller's gateway dispatch
+    /// feeds the resulting voice-state update into [`update_channel`] —
+    /// songbird has no gateway event loop of its own to do this
+    /// automatically.
+    ///
+    /// [`join`]: Call::join
+    /// [`join_gateway`]: Call::join_gateway
+    /// [`update_channel`]: Call::update_channel
     #[instrument(skip(self))]
     pub fn current_channel(&self) -> Option<ChannelId> {
         match &self.connection {
@@ -253,6 +463,85 @@ impl Call {
         }
     }
 
+    /// Sets the policy controlling how this Call reacts to forced channel
+    /// moves and unexpected disconnects.
+    #[instrument(skip(self))]
+    pub fn set_connection_behavior(&mut self, behavior: ConnectionBehavior) {
+        self.behavior = behavior;
+    }
+
+    /// Returns the current connection behaviour policy for this Call.
+    #[instrument(skip(self))]
+    pub fn connection_behavior(&self) -> ConnectionBehavior {
+        self.behavior
+    }
+
+    /// Updates the channel this Call is connected to, in response to a
+    /// voice-state update from the gateway for this user.
+    ///
+    /// You must call this from your own gateway dispatch (the same place
+    /// you'd call [`update_server`]/[`update_state`]) whenever a voice
+    /// state update arrives for this user; songbird does not listen to
+    /// the gateway on its own. Doing so keeps [`current_channel`] accurate
+    /// when a server admin forcibly moves the bot between channels,
+    /// rather than only reflecting the channel that was originally
+    /// requested via [`join`]/[`join_gateway`].
+    ///
+    /// If [`ConnectionBehavior::auto_follow_moves`] is set and the
+    /// `"driver"` feature is enabled, the driver re-connects to the
+    /// (possibly updated) endpoint/session so that audio keeps flowing.
+    ///
+    /// [`update_server`]: Call::update_server
+    /// [`update_state`]: Call::update_state
+    /// [`current_channel`]: Call::current_channel
+    /// [`join`]: Call::join
+    /// [`join_gateway`]: Call::join_gateway
+    #[instrument(skip(self))]
+    pub fn update_channel(&mut self, channel_id: ChannelId) {
+        let moved_from = match &mut self.connection {
+            Some((id, ..)) => replace_if_changed(id, channel_id),
+            _ => None,
+        };
+
+        if let Some(from) = moved_from {
+            self.emit_update(CallUpdate::ChannelMoved {
+                from,
+                to: channel_id,
+            });
+
+            #[cfg(feature = "driver-core")]
+            if self.behavior.auto_follow_moves {
+                self.do_connect();
+            }
+        }
+    }
+
+    /// Notifies this Call that its voice connection was lost unexpectedly,
+    /// e.g. after the driver reports a dead WS/UDP socket.
+    ///
+    /// Like [`update_channel`], this must be called by whatever detects
+    /// the drop — songbird doesn't watch the connection for you. If
+    /// [`ConnectionBehavior::auto_reconnect`] is set, this re-issues the
+    /// op 4 `Update Voice State` payload to ask the gateway to
+    /// re-establish the session; otherwise the Call only notifies any
+    /// [`subscribe`]rs that the connection was lost.
+    ///
+    /// [`update_channel`]: Call::update_channel
+    /// [`subscribe`]: Call::subscribe
+    #[instrument(skip(self))]
+    pub async fn notify_connection_lost(&mut self) -> JoinResult<()> {
+        self.emit_update(CallUpdate::Disconnected {
+            reason: DisconnectReason::Lost,
+        });
+
+        if self.behavior.auto_reconnect {
+            self.emit_update(CallUpdate::Reconnecting);
+            self.update().await
+        } else {
+            Ok(())
+        }
+    }
+
     /// Leaves the current voice channel, disconnecting from it.
     ///
     /// This does _not_ forget settings, like whether to be self-deafened or
@@ -271,6 +560,10 @@ impl Call {
         #[cfg(feature = "driver-core")]
         self.driver.leave();
 
+        self.emit_update(CallUpdate::Disconnected {
+            reason: DisconnectReason::Requested,
+        });
+
         self.update().await
     }
 
@@ -313,6 +606,8 @@ impl Call {
             false
         };
 
+        self.emit_update(CallUpdate::ServerUpdate);
+
         if try_conn {
             self.do_connect();
         }
@@ -332,6 +627,8 @@ impl Call {
             false
         };
 
+        self.emit_update(CallUpdate::StateUpdate);
+
         if try_conn {
             self.do_connect();
         }
@@ -377,3 +674,114 @@ impl DerefMut for Call {
         &mut self.driver
     }
 }
+
+// These tests exercise `broadcast`/`replace_if_changed` directly rather
+// than a real `Call`, because `Call` itself can't be constructed here:
+// `ChannelId`, `GuildId`, `UserId`, and `Shard` live in `crate::id`/
+// `crate::shards`, neither of which exists in this checkout. Once those
+// modules are available, `Call::subscribe`/`update_channel` should gain
+// their own tests driving a real `Call` end-to-end; this is the closest
+// approximation available until then.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broadcast_delivers_to_every_live_subscriber() {
+        let (tx1, rx1) = flume::unbounded();
+        let (tx2, rx2) = flume::unbounded();
+
+        let mut subscribers = vec![tx1, tx2];
+        broadcast(&mut subscribers, 42);
+
+        assert_eq!(rx1.try_recv(), Ok(42));
+        assert_eq!(rx2.try_recv(), Ok(42));
+        assert_eq!(subscribers.len(), 2);
+    }
+
+    #[test]
+    fn broadcast_drops_disconnected_subscribers() {
+        let (tx1, rx1) = flume::unbounded();
+        let (tx2, rx2) = flume::unbounded();
+        drop(rx2);
+
+        let mut subscribers = vec![tx1, tx2];
+        broadcast(&mut subscribers, "hello");
+
+        assert_eq!(subscribers.len(), 1);
+        assert_eq!(rx1.try_recv(), Ok("hello"));
+    }
+
+    #[test]
+    fn a_subscriber_sees_every_update_across_a_calls_lifetime() {
+        // Mirrors the sequence emit_update() produces over join() -> a
+        // forced move -> leave(): several broadcasts against the same
+        // subscriber list, in order, surviving a subscriber dropping out
+        // partway through.
+        let (tx1, rx1) = flume::unbounded();
+        let (tx2, rx2) = flume::unbounded();
+
+        let mut subscribers = vec![tx1, tx2];
+        broadcast(&mut subscribers, "gateway requested");
+        drop(rx2);
+        broadcast(&mut subscribers, "channel moved");
+        broadcast(&mut subscribers, "disconnected");
+
+        assert_eq!(subscribers.len(), 1);
+        assert_eq!(rx1.try_recv(), Ok("gateway requested"));
+        assert_eq!(rx1.try_recv(), Ok("channel moved"));
+        assert_eq!(rx1.try_recv(), Ok("disconnected"));
+    }
+
+    #[test]
+    fn replace_if_changed_reports_the_previous_value_on_a_move() {
+        let mut current = 1u64;
+        assert_eq!(replace_if_changed(&mut current, 2), Some(1));
+        assert_eq!(current, 2);
+    }
+
+    #[test]
+    fn replace_if_changed_is_a_no_op_when_unchanged() {
+        let mut current = 5u64;
+        assert_eq!(replace_if_changed(&mut current, 5), None);
+        assert_eq!(current, 5);
+    }
+
+    #[test]
+    fn update_channel_only_reports_a_move_on_the_update_that_actually_changes_it() {
+        // Mirrors the sequence update_channel() sees across several
+        // voice-state updates: redundant updates for the same channel,
+        // an actual forced move, then more redundant updates.
+        let mut current = 1u64;
+
+        assert_eq!(replace_if_changed(&mut current, 1), None);
+        assert_eq!(replace_if_changed(&mut current, 1), None);
+        assert_eq!(replace_if_changed(&mut current, 2), Some(1));
+        assert_eq!(replace_if_changed(&mut current, 2), None);
+
+        assert_eq!(current, 2);
+    }
+}
+
+#[cfg(all(test, feature = "driver-core"))]
+mod driver_core_tests {
+    use super::*;
+
+    #[test]
+    fn voice_session_round_trips_through_json() {
+        let session = VoiceSession {
+            endpoint: "voice.example.com".into(),
+            token: "tok".into(),
+            session_id: "sess".into(),
+            ssrc: 1234,
+            secret_key: [7u8; 32],
+            encryption_mode: EncryptionMode::AeadAes256GcmRtpSize,
+        };
+
+        let json = serde_json::to_string(&session).expect("VoiceSession should serialize");
+        let round_tripped: VoiceSession =
+            serde_json::from_str(&json).expect("VoiceSession should deserialize");
+
+        assert_eq!(session, round_tripped);
+    }
+}